@@ -1,10 +1,35 @@
+extern crate serde;
+extern crate serde_json;
 extern crate ws;
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::thread;
 use std::error::Error;
+use std::marker::PhantomData;
 use std::sync::mpsc::{channel, Receiver, SendError, Sender};
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use ws::util::Token;
+
+const PING_TOKEN: Token = Token(1);
+
+// Pong-timeout tokens are minted per ping (`pong_timeout_token`) rather than
+// reused, so a stale timeout from an earlier ping can be told apart from the
+// one guarding the ping currently in flight.
+const PONG_TIMEOUT_BASE: usize = 1000;
+
+fn pong_timeout_token(generation: u64) -> Token {
+    Token(PONG_TIMEOUT_BASE + generation as usize)
+}
+
+fn pong_timeout_generation(token: Token) -> Option<u64> {
+    if token.0 >= PONG_TIMEOUT_BASE {
+        Some((token.0 - PONG_TIMEOUT_BASE) as u64)
+    } else {
+        None
+    }
+}
 
 #[derive(Debug)]
 pub enum EasyWsError {
@@ -17,12 +42,20 @@ impl From<SendError<EasyWsCommand>> for EasyWsError {
     }
 }
 
+impl From<ws::Error> for EasyWsError {
+    fn from(_e: ws::Error) -> Self {
+        EasyWsError::Unknown
+    }
+}
+
 pub enum EasyWsCommand {
     Disconnect,
     Send(String),
+    SendBinary(Vec<u8>),
+    Close(ws::CloseCode, String),
 }
 
-#[derive(PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum EasyWsConnectionState {
     Connecting,
     Handshake,
@@ -32,30 +65,220 @@ pub enum EasyWsConnectionState {
 
 pub type EasyWsResult = Result<(), EasyWsError>;
 
+#[derive(Default)]
+struct Callbacks {
+    on_connect_fn: Option<Box<FnMut() + Send>>,
+    on_disconnect_fn: Option<Box<FnMut() + Send>>,
+    on_message_fn: Option<Box<FnMut(&str) + Send>>,
+    on_error_fn: Option<Box<FnMut(&str) + Send>>,
+    on_ping_fn: Option<Box<FnMut(&[u8]) + Send>>,
+    on_pong_fn: Option<Box<FnMut(&[u8]) + Send>>,
+    on_binary_fn: Option<Box<FnMut(&[u8]) + Send>>,
+    on_close_fn: Option<Box<FnMut(ws::CloseCode, &str) + Send>>,
+}
+
+/// Runs `callback` against the callback stored in `callbacks` (selected by
+/// `select`) without holding the mutex locked for the duration of the call:
+/// the slot is taken out, the lock is released, `callback` runs, then the
+/// slot is put back. A callback is allowed to call back into the same
+/// `callbacks` (e.g. `TypedWebSocket::on_message` routing a JSON parse
+/// failure to `on_error`); since `std::sync::Mutex` isn't reentrant, running
+/// it while still locked would deadlock the connection's event-loop thread.
+fn invoke_callback<T, R>(
+    callbacks: &Arc<Mutex<Callbacks>>,
+    select: impl Fn(&mut Callbacks) -> &mut Option<Box<T>>,
+    callback: impl FnOnce(&mut T) -> R,
+) -> Option<R>
+where
+    T: ?Sized,
+{
+    let mut slot = select(&mut callbacks.lock().unwrap()).take();
+    let result = slot.as_mut().map(|func| callback(&mut **func));
+    *select(&mut callbacks.lock().unwrap()) = slot;
+    result
+}
+
+/// Calls `send` for every item, ignoring individual failures: a dead/slow
+/// peer whose `on_close` hasn't run yet must not stop a broadcast from
+/// reaching everyone after it.
+fn send_to_all<'a, T, E>(items: impl Iterator<Item = &'a T>, mut send: impl FnMut(&T) -> Result<(), E>)
+where
+    T: 'a,
+{
+    for item in items {
+        let _ = send(item);
+    }
+}
+
+/// Returns the delay before the next reconnect attempt, following
+/// `delay = min(base * factor^attempt, max)`.
+fn backoff_delay(base: Duration, factor: f64, max: Duration, attempt: u32) -> Duration {
+    let scaled = (base.as_millis() as f64) * factor.powi(attempt as i32);
+    let capped = scaled.min(max.as_millis() as f64);
+    Duration::from_millis(capped as u64)
+}
+
 struct WsClient {
     out: ws::Sender,
+    callbacks: Arc<Mutex<Callbacks>>,
+    state: Arc<Mutex<EasyWsConnectionState>>,
+    current_out: Arc<Mutex<Option<ws::Sender>>>,
+    opened: Arc<Mutex<bool>>,
+    clean_close: Arc<Mutex<bool>>,
+    interval: Duration,
+    disconnect_on_slow_pong: Option<Duration>,
+    ping_generation: Arc<Mutex<u64>>,
+    ping_sent_at: Arc<Mutex<Option<(u64, Instant)>>>,
 }
 
 impl ws::Handler for WsClient {
+    fn on_open(&mut self, _: ws::Handshake) -> ws::Result<()> {
+        *self.state.lock().unwrap() = EasyWsConnectionState::Connected;
+        *self.current_out.lock().unwrap() = Some(self.out.clone());
+        *self.opened.lock().unwrap() = true;
+
+        invoke_callback(&self.callbacks, |cb| &mut cb.on_connect_fn, |func| (func)());
+
+        self.out.timeout(duration_to_millis(self.interval), PING_TOKEN)
+    }
+
     fn on_message(&mut self, msg: ws::Message) -> ws::Result<()> {
+        match msg {
+            ws::Message::Text(text) => {
+                invoke_callback(&self.callbacks, |cb| &mut cb.on_message_fn, |func| (func)(&text));
+            }
+            ws::Message::Binary(data) => {
+                invoke_callback(&self.callbacks, |cb| &mut cb.on_binary_fn, |func| (func)(&data));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn on_close(&mut self, code: ws::CloseCode, reason: &str) {
+        *self.current_out.lock().unwrap() = None;
+        *self.state.lock().unwrap() = EasyWsConnectionState::Disconnected;
+
+        if code == ws::CloseCode::Normal {
+            *self.clean_close.lock().unwrap() = true;
+        }
+
+        invoke_callback(&self.callbacks, |cb| &mut cb.on_close_fn, |func| (func)(code, reason));
+    }
+
+    fn on_error(&mut self, err: ws::Error) {
+        invoke_callback(&self.callbacks, |cb| &mut cb.on_error_fn, |func| (func)(err.description()));
+    }
+
+    fn on_ping(&mut self, data: Vec<u8>) -> ws::Result<()> {
+        invoke_callback(&self.callbacks, |cb| &mut cb.on_ping_fn, |func| (func)(&data));
+
+        self.out.pong(data)
+    }
+
+    fn on_pong(&mut self, data: Vec<u8>) -> ws::Result<()> {
+        *self.ping_sent_at.lock().unwrap() = None;
+
+        invoke_callback(&self.callbacks, |cb| &mut cb.on_pong_fn, |func| (func)(&data));
+
         Ok(())
     }
+
+    fn on_timeout(&mut self, event: Token) -> ws::Result<()> {
+        if event == PING_TOKEN {
+            let generation = {
+                let mut generation = self.ping_generation.lock().unwrap();
+                *generation += 1;
+                *generation
+            };
+
+            self.out.ping(vec![])?;
+
+            // Keep tracking the oldest unanswered ping: overwriting it with
+            // each new ping would let a dead peer escape detection whenever
+            // `interval` is shorter than the slow-pong deadline.
+            let mut ping_sent_at = self.ping_sent_at.lock().unwrap();
+            if ping_sent_at.is_none() {
+                *ping_sent_at = Some((generation, Instant::now()));
+            }
+            drop(ping_sent_at);
+
+            if let Some(deadline) = self.disconnect_on_slow_pong {
+                self.out.timeout(duration_to_millis(deadline), pong_timeout_token(generation))?;
+            }
+
+            return self.out.timeout(duration_to_millis(self.interval), PING_TOKEN);
+        }
+
+        if let Some(generation) = pong_timeout_generation(event) {
+            // Only this token's own ping being still outstanding counts as a
+            // slow pong: the `ws` crate never cancels a previously scheduled
+            // timeout, so without this check a stale timeout belonging to an
+            // already-ponged (or superseded) ping would close a healthy
+            // connection.
+            let still_outstanding = match *self.ping_sent_at.lock().unwrap() {
+                Some((pending_generation, _)) => pending_generation == generation,
+                None => false,
+            };
+
+            if still_outstanding {
+                self.out.close(ws::CloseCode::Away)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn duration_to_millis(duration: Duration) -> u64 {
+    duration.as_secs() * 1000 + u64::from(duration.subsec_millis())
+}
+
+/// Forwards queued `EasyWsCommand`s to whichever connection is currently
+/// live, dropping them silently while disconnected so callers never block
+/// on a send during a reconnect.
+fn run_command_forwarder(rx: Receiver<EasyWsCommand>, current_out: Arc<Mutex<Option<ws::Sender>>>) {
+    for command in rx.iter() {
+        let out = current_out.lock().unwrap().clone();
+
+        let out = match out {
+            Some(out) => out,
+            None => continue,
+        };
+
+        match command {
+            EasyWsCommand::Disconnect => {
+                let _ = out.close(ws::CloseCode::Normal);
+            }
+            EasyWsCommand::Send(msg) => {
+                let _ = out.send(msg);
+            }
+            EasyWsCommand::SendBinary(data) => {
+                let _ = out.send(ws::Message::Binary(data));
+            }
+            EasyWsCommand::Close(code, reason) => {
+                let _ = out.close_with_reason(code, reason);
+            }
+        }
+    }
 }
 
 pub struct SimpleWebSocket {
-    _rx: Receiver<EasyWsCommand>,
-    _tx: Sender<EasyWsCommand>,
+    tx: Sender<EasyWsCommand>,
 
     timeout: Duration,
     interval: Duration,
     endpoint: String,
 
-    on_connect_fn: Option<Box<FnMut() + Send>>,
-    on_disconnect_fn: Option<Box<FnMut() + Send>>,
-    on_message_fn: Option<Box<FnMut(&str) + Send>>,
-    on_error_fn: Option<Box<FnMut(&str) + Send>>,
+    reconnect_base: Duration,
+    reconnect_factor: f64,
+    reconnect_max: Duration,
+    max_retries: Option<u32>,
 
-    state: EasyWsConnectionState,
+    disconnect_on_slow_pong: Option<Duration>,
+
+    callbacks: Arc<Mutex<Callbacks>>,
+    state: Arc<Mutex<EasyWsConnectionState>>,
 }
 
 impl SimpleWebSocket {
@@ -63,35 +286,130 @@ impl SimpleWebSocket {
     where
         S: AsRef<str>,
     {
-        let (tx, rx) = channel();
+        let (tx, _rx) = channel();
 
         SimpleWebSocket {
-            _rx: rx,
-            _tx: tx,
+            tx,
             timeout: Duration::from_millis(timeout_ms),
             interval: Duration::from_millis(interval),
             endpoint: endpoint.as_ref().to_string(),
-            on_connect_fn: None,
-            on_disconnect_fn: None,
-            on_message_fn: None,
-            on_error_fn: None,
-            state: EasyWsConnectionState::Disconnected,
+            reconnect_base: Duration::from_millis(1000),
+            reconnect_factor: 2.0,
+            reconnect_max: Duration::from_millis(60000),
+            max_retries: None,
+            disconnect_on_slow_pong: Some(Duration::from_millis(timeout_ms)),
+            callbacks: Arc::new(Mutex::new(Callbacks::default())),
+            state: Arc::new(Mutex::new(EasyWsConnectionState::Disconnected)),
         }
     }
 
+    pub fn state(&self) -> EasyWsConnectionState {
+        *self.state.lock().unwrap()
+    }
+
+    /// The deadline configured via `SimpleWebSocketBuilder::with_timeout`;
+    /// `with_disconnect_on_slow_pong` defaults to this value.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
     pub fn connect(&mut self) -> EasyWsResult {
-        if self.state != EasyWsConnectionState::Disconnected {
-            ()
+        if self.state() != EasyWsConnectionState::Disconnected {
+            return Ok(());
         }
 
+        // Recreate the command channel on every call: the previous one's
+        // receiver was either dropped in `new` or belongs to the last
+        // connection's forwarder thread, which only forwards to that closed
+        // connection, so reusing it would leave `send`/`disconnect` going
+        // nowhere. Replacing `self.tx` also lets that old forwarder exit.
+        let (tx, rx) = channel();
+        self.tx = tx;
+
+        *self.state.lock().unwrap() = EasyWsConnectionState::Connecting;
+
         let endpoint = self.endpoint.clone();
+        let callbacks = self.callbacks.clone();
+        let state = self.state.clone();
+        let current_out = Arc::new(Mutex::new(None));
+        let reconnect_base = self.reconnect_base;
+        let reconnect_factor = self.reconnect_factor;
+        let reconnect_max = self.reconnect_max;
+        let max_retries = self.max_retries;
+        let interval = self.interval;
+        let disconnect_on_slow_pong = self.disconnect_on_slow_pong;
+
+        {
+            let current_out = current_out.clone();
+            thread::spawn(move || run_command_forwarder(rx, current_out));
+        }
+
+        thread::spawn(move || {
+            let mut attempt: u32 = 0;
+
+            loop {
+                *state.lock().unwrap() = EasyWsConnectionState::Connecting;
+
+                let opened = Arc::new(Mutex::new(false));
+                let clean_close = Arc::new(Mutex::new(false));
+
+                let result = {
+                    let callbacks = callbacks.clone();
+                    let state = state.clone();
+                    let current_out = current_out.clone();
+                    let opened = opened.clone();
+                    let clean_close = clean_close.clone();
+
+                    ws::connect(endpoint.clone(), move |out| WsClient {
+                        out,
+                        callbacks: callbacks.clone(),
+                        state: state.clone(),
+                        current_out: current_out.clone(),
+                        opened: opened.clone(),
+                        clean_close: clean_close.clone(),
+                        interval,
+                        disconnect_on_slow_pong,
+                        ping_generation: Arc::new(Mutex::new(0)),
+                        ping_sent_at: Arc::new(Mutex::new(None)),
+                    })
+                };
+
+                if let Err(ref error) = result {
+                    if let Some(func) = callbacks.lock().unwrap().on_error_fn.as_mut() {
+                        (func)(error.description());
+                    }
+                }
+
+                if *opened.lock().unwrap() {
+                    attempt = 0;
+                }
 
-        thread::spawn(|| {
-            // start websocket event-loop
-            if let Err(error) = ws::connect(endpoint, |out| WsClient { out: out }) {
-                if let Some(func) = self.on_error_fn {
-                    (func)(error.description());
+                if *clean_close.lock().unwrap() {
+                    *state.lock().unwrap() = EasyWsConnectionState::Disconnected;
+                    break;
                 }
+
+                if let Some(max) = max_retries {
+                    if attempt >= max {
+                        *state.lock().unwrap() = EasyWsConnectionState::Disconnected;
+
+                        if let Some(func) = callbacks.lock().unwrap().on_error_fn.as_mut() {
+                            (func)("reconnect retries exhausted");
+                        }
+
+                        break;
+                    }
+                }
+
+                *state.lock().unwrap() = EasyWsConnectionState::Connecting;
+
+                if let Some(func) = callbacks.lock().unwrap().on_disconnect_fn.as_mut() {
+                    (func)();
+                }
+
+                let delay = backoff_delay(reconnect_base, reconnect_factor, reconnect_max, attempt);
+                thread::sleep(delay);
+                attempt += 1;
             }
         });
 
@@ -99,11 +417,27 @@ impl SimpleWebSocket {
     }
 
     pub fn disconnect(&mut self) -> EasyWsResult {
-        if self.state == EasyWsConnectionState::Disconnected {
-            ()
+        if self.state() == EasyWsConnectionState::Disconnected {
+            return Ok(());
         }
 
-        self._tx.send(EasyWsCommand::Disconnect)?;
+        self.tx.send(EasyWsCommand::Disconnect)?;
+
+        Ok(())
+    }
+
+    /// Closes the connection with an explicit WebSocket close code and
+    /// reason. A `CloseCode::Normal` close is treated as a clean shutdown
+    /// and does not trigger a reconnect, unlike an error-driven close.
+    pub fn disconnect_with<S>(&mut self, code: ws::CloseCode, reason: S) -> EasyWsResult
+    where
+        S: AsRef<str>,
+    {
+        if self.state() == EasyWsConnectionState::Disconnected {
+            return Ok(());
+        }
+
+        self.tx.send(EasyWsCommand::Close(code, reason.as_ref().to_string()))?;
 
         Ok(())
     }
@@ -113,7 +447,16 @@ impl SimpleWebSocket {
         S: AsRef<str>,
     {
         let msg = message.as_ref().to_string();
-        self._tx.send(EasyWsCommand::Send(msg))?;
+        self.tx.send(EasyWsCommand::Send(msg))?;
+
+        Ok(())
+    }
+
+    pub fn send_binary<B>(&mut self, data: B) -> EasyWsResult
+    where
+        B: Into<Vec<u8>>,
+    {
+        self.tx.send(EasyWsCommand::SendBinary(data.into()))?;
 
         Ok(())
     }
@@ -122,28 +465,56 @@ impl SimpleWebSocket {
     where
         F: 'static + FnMut() + Send,
     {
-        self.on_connect_fn = Some(Box::new(callback));
+        self.callbacks.lock().unwrap().on_connect_fn = Some(Box::new(callback));
     }
 
     pub fn on_disconnect<F>(&mut self, callback: F)
     where
         F: 'static + FnMut() + Send,
     {
-        self.on_disconnect_fn = Some(Box::new(callback));
+        self.callbacks.lock().unwrap().on_disconnect_fn = Some(Box::new(callback));
     }
 
     pub fn on_message<F>(&mut self, callback: F)
     where
         F: 'static + FnMut(&str) + Send,
     {
-        self.on_message_fn = Some(Box::new(callback));
+        self.callbacks.lock().unwrap().on_message_fn = Some(Box::new(callback));
     }
 
     pub fn on_error<F>(&mut self, callback: F)
     where
         F: 'static + FnMut(&str) + Send,
     {
-        self.on_error_fn = Some(Box::new(callback));
+        self.callbacks.lock().unwrap().on_error_fn = Some(Box::new(callback));
+    }
+
+    pub fn on_ping<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(&[u8]) + Send,
+    {
+        self.callbacks.lock().unwrap().on_ping_fn = Some(Box::new(callback));
+    }
+
+    pub fn on_pong<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(&[u8]) + Send,
+    {
+        self.callbacks.lock().unwrap().on_pong_fn = Some(Box::new(callback));
+    }
+
+    pub fn on_binary<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(&[u8]) + Send,
+    {
+        self.callbacks.lock().unwrap().on_binary_fn = Some(Box::new(callback));
+    }
+
+    pub fn on_close<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(ws::CloseCode, &str) + Send,
+    {
+        self.callbacks.lock().unwrap().on_close_fn = Some(Box::new(callback));
     }
 }
 
@@ -151,6 +522,15 @@ pub struct SimpleWebSocketBuilder<'a> {
     timeout: u64,
     interval: u64,
     endpoint: Cow<'a, str>,
+
+    reconnect_base_ms: u64,
+    reconnect_factor: f64,
+    reconnect_max_ms: u64,
+    max_retries: Option<u32>,
+
+    // `None` means "not set, derive from `timeout`"; `Some(None)` means
+    // explicitly disabled via `with_disconnect_on_slow_pong(None)`.
+    disconnect_on_slow_pong_ms: Option<Option<u64>>,
 }
 
 impl<'a> SimpleWebSocketBuilder<'a> {
@@ -162,6 +542,11 @@ impl<'a> SimpleWebSocketBuilder<'a> {
             timeout: 10000,
             interval: 1000,
             endpoint: endpoint.into(),
+            reconnect_base_ms: 1000,
+            reconnect_factor: 2.0,
+            reconnect_max_ms: 60000,
+            max_retries: None,
+            disconnect_on_slow_pong_ms: None,
         }
     }
 
@@ -175,7 +560,389 @@ impl<'a> SimpleWebSocketBuilder<'a> {
         self
     }
 
+    /// Configures the exponential backoff schedule used between reconnect
+    /// attempts: `delay = min(base_ms * factor^attempt, max_ms)`.
+    pub fn with_reconnect_backoff(
+        &mut self,
+        base_ms: u64,
+        factor: f64,
+        max_ms: u64,
+    ) -> &mut SimpleWebSocketBuilder<'a> {
+        self.reconnect_base_ms = base_ms;
+        self.reconnect_factor = factor;
+        self.reconnect_max_ms = max_ms;
+        self
+    }
+
+    /// Caps the number of consecutive reconnect attempts. `None` retries
+    /// forever.
+    pub fn with_max_retries(&mut self, max_retries: Option<u32>) -> &mut SimpleWebSocketBuilder<'a> {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Controls dead-peer detection: if the server doesn't answer a ping
+    /// with a pong within this many milliseconds, the connection is closed
+    /// and the reconnect path takes over. `None` disables the check, so a
+    /// half-open connection can block forever instead. Defaults to
+    /// `with_timeout`'s value; call this to use a different deadline than
+    /// the one passed to `with_timeout`.
+    pub fn with_disconnect_on_slow_pong(
+        &mut self,
+        milliseconds: Option<u64>,
+    ) -> &mut SimpleWebSocketBuilder<'a> {
+        self.disconnect_on_slow_pong_ms = Some(milliseconds);
+        self
+    }
+
     pub fn build(&self) -> SimpleWebSocket {
-        SimpleWebSocket::new(&self.endpoint, self.timeout, self.interval)
+        let mut ws = SimpleWebSocket::new(&self.endpoint, self.timeout, self.interval);
+
+        ws.reconnect_base = Duration::from_millis(self.reconnect_base_ms);
+        ws.reconnect_factor = self.reconnect_factor;
+        ws.reconnect_max = Duration::from_millis(self.reconnect_max_ms);
+        ws.max_retries = self.max_retries;
+
+        let disconnect_on_slow_pong_ms = self.disconnect_on_slow_pong_ms.unwrap_or(Some(self.timeout));
+        ws.disconnect_on_slow_pong = disconnect_on_slow_pong_ms.map(Duration::from_millis);
+
+        ws
+    }
+
+    /// Builds a [`TypedWebSocket`], delivering deserialized `T` values to
+    /// `on_message` instead of raw `&str` text frames.
+    pub fn typed<T>(&self) -> TypedWebSocket<T>
+    where
+        T: serde::de::DeserializeOwned + serde::Serialize,
+    {
+        TypedWebSocket {
+            inner: self.build(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A `SimpleWebSocket` wrapper that serializes outgoing messages and
+/// deserializes incoming text frames as `T` via `serde_json`, so callers
+/// work with strongly-typed values instead of raw JSON strings. Parse
+/// failures are routed to the `on_error` callback rather than `on_message`.
+pub struct TypedWebSocket<T> {
+    inner: SimpleWebSocket,
+    _marker: PhantomData<T>,
+}
+
+impl<T> TypedWebSocket<T>
+where
+    T: serde::de::DeserializeOwned + serde::Serialize,
+{
+    pub fn state(&self) -> EasyWsConnectionState {
+        self.inner.state()
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.inner.timeout()
+    }
+
+    pub fn connect(&mut self) -> EasyWsResult {
+        self.inner.connect()
+    }
+
+    pub fn disconnect(&mut self) -> EasyWsResult {
+        self.inner.disconnect()
+    }
+
+    pub fn disconnect_with<S>(&mut self, code: ws::CloseCode, reason: S) -> EasyWsResult
+    where
+        S: AsRef<str>,
+    {
+        self.inner.disconnect_with(code, reason)
+    }
+
+    pub fn send(&mut self, message: &T) -> EasyWsResult {
+        let payload = serde_json::to_string(message).map_err(|_| EasyWsError::Unknown)?;
+        self.inner.send(payload)
+    }
+
+    pub fn on_connect<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut() + Send,
+    {
+        self.inner.on_connect(callback);
+    }
+
+    pub fn on_disconnect<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut() + Send,
+    {
+        self.inner.on_disconnect(callback);
+    }
+
+    pub fn on_message<F>(&mut self, mut callback: F)
+    where
+        F: 'static + FnMut(T) + Send,
+    {
+        let callbacks = self.inner.callbacks.clone();
+
+        self.inner.on_message(move |text: &str| match serde_json::from_str::<T>(text) {
+            Ok(value) => callback(value),
+            Err(error) => {
+                invoke_callback(&callbacks, |cb| &mut cb.on_error_fn, |func| (func)(&error.to_string()));
+            }
+        });
+    }
+
+    pub fn on_error<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(&str) + Send,
+    {
+        self.inner.on_error(callback);
+    }
+
+    pub fn on_ping<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(&[u8]) + Send,
+    {
+        self.inner.on_ping(callback);
+    }
+
+    pub fn on_pong<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(&[u8]) + Send,
+    {
+        self.inner.on_pong(callback);
+    }
+
+    pub fn on_binary<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(&[u8]) + Send,
+    {
+        self.inner.on_binary(callback);
+    }
+
+    pub fn on_close<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(ws::CloseCode, &str) + Send,
+    {
+        self.inner.on_close(callback);
+    }
+}
+
+/// Identifies a single connected peer on a `SimpleWebSocketServer`, handed
+/// out when the connection is accepted.
+pub type ConnectionId = u64;
+
+#[derive(Default)]
+struct ServerCallbacks {
+    on_connect_fn: Option<Box<FnMut(ConnectionId) + Send>>,
+    on_disconnect_fn: Option<Box<FnMut(ConnectionId) + Send>>,
+    on_message_fn: Option<Box<FnMut(ConnectionId, &str) + Send>>,
+    on_binary_fn: Option<Box<FnMut(ConnectionId, &[u8]) + Send>>,
+}
+
+struct WsServerClient {
+    id: ConnectionId,
+    out: ws::Sender,
+    callbacks: Arc<Mutex<ServerCallbacks>>,
+    registry: Arc<Mutex<HashMap<ConnectionId, ws::Sender>>>,
+    next_id: Arc<Mutex<ConnectionId>>,
+}
+
+impl ws::Handler for WsServerClient {
+    fn on_open(&mut self, _: ws::Handshake) -> ws::Result<()> {
+        let id = {
+            let mut next_id = self.next_id.lock().unwrap();
+            let id = *next_id;
+            *next_id += 1;
+            id
+        };
+
+        self.id = id;
+        self.registry.lock().unwrap().insert(id, self.out.clone());
+
+        if let Some(func) = self.callbacks.lock().unwrap().on_connect_fn.as_mut() {
+            (func)(id);
+        }
+
+        Ok(())
+    }
+
+    fn on_message(&mut self, msg: ws::Message) -> ws::Result<()> {
+        match msg {
+            ws::Message::Text(text) => {
+                if let Some(func) = self.callbacks.lock().unwrap().on_message_fn.as_mut() {
+                    (func)(self.id, &text);
+                }
+            }
+            ws::Message::Binary(data) => {
+                if let Some(func) = self.callbacks.lock().unwrap().on_binary_fn.as_mut() {
+                    (func)(self.id, &data);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn on_close(&mut self, _code: ws::CloseCode, _reason: &str) {
+        self.registry.lock().unwrap().remove(&self.id);
+
+        if let Some(func) = self.callbacks.lock().unwrap().on_disconnect_fn.as_mut() {
+            (func)(self.id);
+        }
+    }
+}
+
+/// A symmetric counterpart to `SimpleWebSocket`: accepts connections on
+/// `bind_addr`, keeps a registry of connected peers, and lets the
+/// application `broadcast` to all of them or `send_to` a single one.
+pub struct SimpleWebSocketServer {
+    bind_addr: String,
+    callbacks: Arc<Mutex<ServerCallbacks>>,
+    registry: Arc<Mutex<HashMap<ConnectionId, ws::Sender>>>,
+    next_id: Arc<Mutex<ConnectionId>>,
+}
+
+impl SimpleWebSocketServer {
+    pub fn new<S>(bind_addr: S) -> SimpleWebSocketServer
+    where
+        S: AsRef<str>,
+    {
+        SimpleWebSocketServer {
+            bind_addr: bind_addr.as_ref().to_string(),
+            callbacks: Arc::new(Mutex::new(ServerCallbacks::default())),
+            registry: Arc::new(Mutex::new(HashMap::new())),
+            next_id: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// Starts `ws::listen` on a background thread so the calling thread can
+    /// keep using `broadcast`/`send_to` afterwards.
+    pub fn listen(&self) -> EasyWsResult {
+        let bind_addr = self.bind_addr.clone();
+        let callbacks = self.callbacks.clone();
+        let registry = self.registry.clone();
+        let next_id = self.next_id.clone();
+
+        thread::spawn(move || {
+            let _ = ws::listen(bind_addr, move |out| WsServerClient {
+                id: 0,
+                out,
+                callbacks: callbacks.clone(),
+                registry: registry.clone(),
+                next_id: next_id.clone(),
+            });
+        });
+
+        Ok(())
+    }
+
+    pub fn on_connect<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(ConnectionId) + Send,
+    {
+        self.callbacks.lock().unwrap().on_connect_fn = Some(Box::new(callback));
+    }
+
+    pub fn on_disconnect<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(ConnectionId) + Send,
+    {
+        self.callbacks.lock().unwrap().on_disconnect_fn = Some(Box::new(callback));
+    }
+
+    pub fn on_message<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(ConnectionId, &str) + Send,
+    {
+        self.callbacks.lock().unwrap().on_message_fn = Some(Box::new(callback));
+    }
+
+    pub fn on_binary<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(ConnectionId, &[u8]) + Send,
+    {
+        self.callbacks.lock().unwrap().on_binary_fn = Some(Box::new(callback));
+    }
+
+    pub fn broadcast<S>(&self, message: S) -> EasyWsResult
+    where
+        S: AsRef<str>,
+    {
+        let msg = message.as_ref().to_string();
+
+        send_to_all(self.registry.lock().unwrap().values(), |out| out.send(msg.clone()));
+
+        Ok(())
+    }
+
+    pub fn broadcast_binary<B>(&self, data: B) -> EasyWsResult
+    where
+        B: Into<Vec<u8>>,
+    {
+        let data = data.into();
+
+        send_to_all(self.registry.lock().unwrap().values(), |out| {
+            out.send(ws::Message::Binary(data.clone()))
+        });
+
+        Ok(())
+    }
+
+    pub fn send_to<S>(&self, id: ConnectionId, message: S) -> EasyWsResult
+    where
+        S: AsRef<str>,
+    {
+        if let Some(out) = self.registry.lock().unwrap().get(&id) {
+            out.send(message.as_ref().to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_delay_grows_exponentially_then_caps() {
+        let base = Duration::from_millis(1000);
+        let max = Duration::from_millis(60000);
+
+        assert_eq!(backoff_delay(base, 2.0, max, 0), Duration::from_millis(1000));
+        assert_eq!(backoff_delay(base, 2.0, max, 1), Duration::from_millis(2000));
+        assert_eq!(backoff_delay(base, 2.0, max, 2), Duration::from_millis(4000));
+        assert_eq!(backoff_delay(base, 2.0, max, 6), Duration::from_millis(60000));
+    }
+
+    #[test]
+    fn pong_timeout_token_round_trips_through_generation() {
+        for generation in &[0u64, 1, 2, 500] {
+            let token = pong_timeout_token(*generation);
+            assert_eq!(pong_timeout_generation(token), Some(*generation));
+        }
+    }
+
+    #[test]
+    fn pong_timeout_generation_rejects_unrelated_tokens() {
+        assert_eq!(pong_timeout_generation(PING_TOKEN), None);
+    }
+
+    #[test]
+    fn send_to_all_continues_past_a_failed_send() {
+        let items = vec![1, 2, 3];
+        let mut attempted = Vec::new();
+
+        send_to_all(items.iter(), |&i| -> Result<(), ()> {
+            attempted.push(i);
+
+            if i == 2 {
+                Err(())
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(attempted, vec![1, 2, 3]);
     }
 }